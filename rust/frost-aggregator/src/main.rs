@@ -5,11 +5,16 @@ use clap::Parser;
 use frost_ed25519 as frost;
 use frost::keys::{PublicKeyPackage, VerifyingShare};
 use frost::{round1::SigningCommitments, round2::SignatureShare};
+use futures::future::join_all;
 use hex::FromHex;
+use rand::RngCore;
+use rand::rngs::OsRng;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use tokio::runtime::Runtime;
+use sha2::{Digest, Sha512};
 use std::convert::TryInto;
+use tokio::runtime::Runtime;
+use tracing::{info, warn};
 
 /// JSON shapes identical to those served by signer daemons
 #[derive(Serialize, Deserialize)]
@@ -25,6 +30,7 @@ struct NonceResponse {
 #[derive(Serialize, Deserialize)]
 struct SignRequest {
     package: frost::SigningPackage,
+    randomizer_hex: Option<String>,
 }
 #[derive(Serialize, Deserialize)]
 struct SignResponse {
@@ -38,108 +44,540 @@ struct GroupKeyFile {
     verifying_key: String,
 }
 
+/// Which elliptic-curve ciphersuite the signer pool is operating under.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum Ciphersuite {
+    Ed25519,
+    Secp256k1Tr,
+}
+
 #[derive(Parser, Debug)]
-#[command(about = "Aggregator that collects FROST signature shares and outputs the aggregated signature.")]
+#[command(about = "Aggregator that collects FROST signature shares from a t-of-n signer pool and outputs the aggregated signature.")]
 struct Args {
     /// Hex-encoded message to sign (Schnorr pre-hash message)
     #[arg(long)]
     msg_hex: String,
 
-    /// Comma-separated list of signer base URLs, e.g. http://127.0.0.1:3001
+    /// Comma-separated pool of signer base URLs, e.g. http://127.0.0.1:3001.
+    /// May contain more than `threshold` entries; the extras are used as
+    /// standby replacements if a signer is offline or misbehaves.
     #[arg(long)]
     signers: String,
 
+    /// Signing threshold (t). The aggregator only needs this many honest,
+    /// responsive signers out of the whole pool.
+    #[arg(long)]
+    threshold: u16,
+
     /// Path to group_public_key.json produced during DKG
     #[arg(long, default_value = "frost-artifacts/group_public_key.json")]
     group_key: PathBuf,
+
+    /// Rerandomize the signature (Zcash-style): the daemons sign under a
+    /// fresh per-signing randomizer ρ instead of the group's ordinary key,
+    /// so the resulting signature is only valid under the tweaked key
+    /// `VK' = VK + ρ·B` and is unlinkable to other signatures from the same
+    /// group. Only supported for `--ciphersuite ed25519`.
+    #[arg(long)]
+    rerandomize: bool,
+
+    /// Ciphersuite the signer pool is operating under
+    #[arg(long, value_enum, default_value = "ed25519")]
+    ciphersuite: Ciphersuite,
+
+    /// Hex-encoded BIP341 script-tree merkle root to tweak the Taproot
+    /// output key with. Ignored for `--ciphersuite ed25519`; omit for a
+    /// key-path-only (script-less) Taproot output.
+    #[arg(long)]
+    merkle_root_hex: Option<String>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    if args.rerandomize && args.ciphersuite == Ciphersuite::Secp256k1Tr {
+        return Err(anyhow!("--rerandomize is only supported for --ciphersuite ed25519"));
+    }
+    if args.rerandomize {
+        eprintln!(
+            "⚠ --rerandomize: per-share verification is skipped this run. frost_rerandomized \
+             has no rerandomized counterpart to frost_core::verify_signature_share, so a bad \
+             share is no longer caught and evicted individually — it only surfaces later as an \
+             opaque aggregate-signature-verify failure, with no culprit named."
+        );
+    }
+
     let msg_bytes = <Vec<u8>>::from_hex(&args.msg_hex).map_err(|_| anyhow!("invalid msg_hex"))?;
 
     let rt = Runtime::new()?;
-    let sig_bytes = rt.block_on(async { coordinator_run(&args, &msg_bytes).await })?;
-
-    println!("Aggregated signature (hex): {}", hex::encode(sig_bytes));
+    match args.ciphersuite {
+        Ciphersuite::Ed25519 => {
+            let outcome = rt.block_on(async { coordinator_run(&args, &msg_bytes).await })?;
+            println!("Aggregated signature (hex): {}", hex::encode(outcome.signature));
+            if let Some(randomized) = outcome.randomized {
+                println!("Rerandomized verifying key (hex): {}", randomized.verifying_key_hex);
+                println!("Rerandomized address (base58): {}", randomized.address_base58);
+            }
+        }
+        Ciphersuite::Secp256k1Tr => {
+            let outcome = rt.block_on(async { coordinator_run_tr(&args, &msg_bytes).await })?;
+            println!("Aggregated signature (hex): {}", hex::encode(outcome.signature));
+            println!("Taproot output key (x-only hex): {}", outcome.output_key_hex);
+        }
+    }
     Ok(())
 }
 
-async fn coordinator_run(args: &Args, message: &[u8]) -> Result<Vec<u8>> {
+/// Present only when `--rerandomize` was used: the one-time tweaked key the
+/// signature is valid under, so the caller knows which address to fund or
+/// check the signature against (the ordinary group key will NOT verify it).
+struct RandomizedOutcome {
+    verifying_key_hex: String,
+    address_base58: String,
+}
+
+struct CoordinatorOutcome {
+    signature: Vec<u8>,
+    randomized: Option<RandomizedOutcome>,
+}
+
+/// A signer that has been asked for round1 commitments and responded, kept
+/// around (URL + identifier + commitments) so it can be re-used as a
+/// standby without repeating the `/nonce` round-trip.
+struct Candidate {
+    url: String,
+    identifier: frost::Identifier,
+    commitments: SigningCommitments,
+}
+
+async fn fetch_candidate(client: &Client, url: &str, message: &[u8]) -> Result<Candidate> {
+    let resp: NonceResponse = client
+        .post(format!("{url}/nonce"))
+        .json(&NonceRequest { message: hex::encode(message) })
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    let id_bytes = <Vec<u8>>::from_hex(resp.participant_id)?;
+    if id_bytes.len() < 2 {
+        return Err(anyhow!("identifier bytes too short"));
+    }
+    let id_u16 = u16::from_le_bytes([id_bytes[0], id_bytes[1]]);
+    let identifier: frost::Identifier = id_u16.try_into().map_err(|e| anyhow!(format!("identifier err: {:?}", e)))?;
+
+    Ok(Candidate {
+        url: url.to_string(),
+        identifier,
+        commitments: resp.commitments,
+    })
+}
+
+async fn request_share(
+    client: &Client,
+    candidate: &Candidate,
+    signing_package: &frost::SigningPackage,
+    randomizer_hex: Option<&str>,
+) -> Result<SignatureShare> {
+    let resp: SignResponse = client
+        .post(format!("{}/sign", candidate.url))
+        .json(&SignRequest {
+            package: signing_package.clone(),
+            randomizer_hex: randomizer_hex.map(str::to_owned),
+        })
+        .send()
+        .await?
+        .json()
+        .await?;
+    Ok(resp.share)
+}
+
+/// Derive a per-signing randomizer bound to the message being signed plus
+/// fresh entropy, the same hash-then-reduce construction as the CLI's own
+/// `derive_randomizer`: binding the message means a
+/// coordinator bug that reused a randomizer across two different messages
+/// still can't be exploited to recover anything about the shares, since
+/// each randomizer is also unique per run.
+fn derive_randomizer(message: &[u8]) -> frost_rerandomized::Randomizer<frost::Ed25519Sha512> {
+    let mut entropy = [0u8; 32];
+    OsRng.fill_bytes(&mut entropy);
+
+    let mut hasher = Sha512::new();
+    hasher.update(b"cb-mpc/frost-rerandomize");
+    hasher.update(message);
+    hasher.update(entropy);
+    let hash: [u8; 64] = hasher.finalize().into();
+
+    let scalar_bytes = curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&hash).to_bytes();
+    frost_rerandomized::Randomizer::deserialize(&scalar_bytes)
+        .expect("a uniformly reduced scalar is always a valid randomizer")
+}
+
+async fn coordinator_run(args: &Args, message: &[u8]) -> Result<CoordinatorOutcome> {
     let client = Client::new();
 
-    let signer_urls: Vec<String> = args
+    let pool: Vec<String> = args
         .signers
         .split(',')
         .map(|s| s.trim().to_owned())
         .filter(|s| !s.is_empty())
         .collect();
 
-    if signer_urls.len() < 2 {
-        return Err(anyhow!("need at least two signer URLs"));
+    if pool.len() < args.threshold as usize {
+        return Err(anyhow!("signer pool ({}) is smaller than the threshold ({})", pool.len(), args.threshold));
     }
 
-    // 1. Round-1: ask each signer for its commitments
-    let mut commitments_map: BTreeMap<frost::Identifier, SigningCommitments> = BTreeMap::new();
-    for url in &signer_urls {
-        let resp: NonceResponse = client
-            .post(format!("{url}/nonce"))
-            .json(&NonceRequest {
-                message: hex::encode(message),
-            })
-            .send()
-            .await?
-            .json()
-            .await?;
+    // Load the group's public key package once; we'll need it both to
+    // verify individual shares and to perform the final aggregation.
+    let gkf: GroupKeyFile = serde_json::from_reader(File::open(&args.group_key)?)?;
+    let pubkey_package = load_pubkey_package(gkf)?;
+
+    // Fixed for the whole session: every daemon in the quorum signs under
+    // the same randomizer, and re-signing after an eviction reuses it too,
+    // since the output must be valid under a single VK'.
+    let randomizer = args.rerandomize.then(|| derive_randomizer(message));
+    let randomizer_hex = randomizer.as_ref().map(|r| hex::encode(r.serialize()));
+    let randomized_params = randomizer
+        .map(|r| frost_rerandomized::RandomizedParams::from_randomizer(pubkey_package.verifying_key(), r));
 
-        let id_bytes = <Vec<u8>>::from_hex(resp.participant_id)?;
-        if id_bytes.len() < 2 {
-            return Err(anyhow!("identifier bytes too short"));
+    // 1. Round-1: fetch commitments from the whole pool in parallel. Each
+    // candidate's identifier and commitments are cached here, so a signer
+    // later picked as a standby needs no second /nonce call.
+    let results = join_all(pool.iter().map(|url| fetch_candidate(&client, url, message))).await;
+    let mut candidates: Vec<Candidate> = Vec::new();
+    for (url, result) in pool.iter().zip(results) {
+        match result {
+            Ok(candidate) => candidates.push(candidate),
+            Err(e) => warn!("signer {url} did not return round1 commitments: {e}"),
         }
-        let id_u16 = u16::from_le_bytes([id_bytes[0], id_bytes[1]]);
-        let identifier: frost::Identifier = id_u16.try_into().map_err(|e| anyhow!(format!("identifier err: {:?}", e)))?;
+    }
+    if candidates.len() < args.threshold as usize {
+        return Err(anyhow!(
+            "only {} of {} signers responded to round1, need {}",
+            candidates.len(),
+            pool.len(),
+            args.threshold
+        ));
+    }
 
-        commitments_map.insert(identifier, resp.commitments);
-    }
-
-    // Build SigningPackage
-    let signing_package = frost::SigningPackage::new(commitments_map.clone(), message);
-
-    // 2. Round-2: request signature share from each signer
-    let mut shares: BTreeMap<frost::Identifier, SignatureShare> = BTreeMap::new();
-    for url in &signer_urls {
-        let id_resp: NonceResponse = client
-            .post(format!("{url}/nonce"))
-            .json(&NonceRequest {
-                message: hex::encode(message),
-            })
-            .send()
-            .await?
-            .json()
-            .await?;
-        let id_bytes = <Vec<u8>>::from_hex(id_resp.participant_id)?;
-        let id_u16 = u16::from_le_bytes([id_bytes[0], id_bytes[1]]);
-        let identifier: frost::Identifier = id_u16.try_into().map_err(|e| anyhow!(format!("identifier err: {:?}", e)))?;
+    // 2. Build an initial quorum of exactly `threshold` responsive
+    // signers, keeping the rest as standbys.
+    let mut active: Vec<Candidate> = candidates.drain(..args.threshold as usize).collect();
+    let mut standbys: Vec<Candidate> = candidates;
+
+    // 3. Round-2, with verification and culprit eviction. A quorum member
+    // that times out or returns a share that fails individual verification
+    // is replaced by a standby and the whole quorum re-signs, since FROST
+    // binds every share to the exact set of commitments in the signing
+    // package.
+    loop {
+        let commitments_map: BTreeMap<frost::Identifier, SigningCommitments> =
+            active.iter().map(|c| (c.identifier, c.commitments)).collect();
+        let signing_package = frost::SigningPackage::new(commitments_map, message);
+
+        let share_results = join_all(
+            active
+                .iter()
+                .map(|c| request_share(&client, c, &signing_package, randomizer_hex.as_deref())),
+        )
+        .await;
+
+        let mut shares: BTreeMap<frost::Identifier, SignatureShare> = BTreeMap::new();
+        let mut culprits: Vec<usize> = Vec::new();
+        for (idx, (candidate, result)) in active.iter().zip(share_results).enumerate() {
+            let share = match result {
+                Ok(share) => share,
+                Err(e) => {
+                    warn!("signer {} ({}) failed to produce a share: {e}", candidate.url, hex::encode(candidate.identifier.serialize()));
+                    culprits.push(idx);
+                    continue;
+                }
+            };
+
+            // `frost::round2::verify_signature_share` checks a share against
+            // the group's ordinary verifying share; it has no rerandomized
+            // counterpart in this crate version, so a rerandomized session
+            // skips per-share verification here and instead verifies the
+            // final aggregated signature below. A bad share in that case
+            // surfaces as an aggregate-verify failure rather than naming a
+            // culprit, so the whole quorum would need re-running by hand.
+            if randomized_params.is_none() {
+                let verifying_share = pubkey_package
+                    .verifying_shares()
+                    .get(&candidate.identifier)
+                    .ok_or_else(|| anyhow!("no verifying share for {}", hex::encode(candidate.identifier.serialize())))?;
+
+                if let Err(e) = frost_core::verify_signature_share::<frost::Ed25519Sha512>(
+                    candidate.identifier,
+                    verifying_share,
+                    &share,
+                    &signing_package,
+                    pubkey_package.verifying_key(),
+                ) {
+                    warn!(
+                        "signer {} ({}) submitted an invalid signature share and is being excluded: {e:?}",
+                        candidate.url,
+                        hex::encode(candidate.identifier.serialize())
+                    );
+                    culprits.push(idx);
+                    continue;
+                }
+            }
+
+            shares.insert(candidate.identifier, share);
+        }
+
+        if culprits.is_empty() {
+            info!("collected a quorum of {} shares, aggregating", shares.len());
+            return Ok(match &randomized_params {
+                None => {
+                    let signature = frost::aggregate(&signing_package, &shares, &pubkey_package)
+                        .map_err(|e| anyhow!(format!("aggregate err: {:?}", e)))?;
+                    CoordinatorOutcome {
+                        signature: signature.serialize().map_err(|e| anyhow!(format!("serialize err: {:?}", e)))?,
+                        randomized: None,
+                    }
+                }
+                Some(randomized_params) => {
+                    let signature = frost_rerandomized::aggregate(&signing_package, &shares, &pubkey_package, randomized_params)
+                        .map_err(|e| anyhow!(format!("rerandomized aggregate err: {:?}", e)))?;
+                    let randomized_vk = randomized_params.randomized_verifying_key();
+                    let vk_bytes = randomized_vk.serialize().map_err(|e| anyhow!(format!("vk serialize err: {:?}", e)))?;
+                    CoordinatorOutcome {
+                        signature: signature.serialize().map_err(|e| anyhow!(format!("serialize err: {:?}", e)))?,
+                        randomized: Some(RandomizedOutcome {
+                            verifying_key_hex: hex::encode(&vk_bytes),
+                            address_base58: bs58::encode(&vk_bytes).into_string(),
+                        }),
+                    }
+                }
+            });
+        }
+
+        // Evict culprits (highest index first so removal doesn't shift the
+        // indices we still need to remove) and pull in replacements.
+        for idx in culprits.into_iter().rev() {
+            active.remove(idx);
+        }
+        // Members kept in the quorum spent their nonces on the /sign just
+        // made; fetch fresh commitments for them before re-signing.
+        let refreshed = join_all(active.iter().map(|c| fetch_candidate(&client, &c.url, message))).await;
+        active = refreshed
+            .into_iter()
+            .filter_map(|r| r.map_err(|e| warn!("signer dropped while refreshing commitments: {e}")).ok())
+            .collect();
+        while active.len() < args.threshold as usize {
+            match standbys.pop() {
+                Some(replacement) => active.push(replacement),
+                None => return Err(anyhow!("not enough honest, responsive signers left in the pool to reach the threshold")),
+            }
+        }
+    }
+}
+
+// ========= Secp256k1-Taproot coordination =========
+//
+// Structurally identical to the ed25519 path above (same fault-tolerant
+// fetch/sign/verify/evict loop), over the BIP340 ciphersuite and with the
+// group key tweaked per BIP341 before aggregation.
+
+struct CandidateTr {
+    url: String,
+    identifier: frost_secp256k1_tr::Identifier,
+    commitments: frost_secp256k1_tr::round1::SigningCommitments,
+}
+
+struct CoordinatorOutcomeTr {
+    signature: Vec<u8>,
+    output_key_hex: String,
+}
+
+async fn fetch_candidate_tr(client: &Client, url: &str, message: &[u8]) -> Result<CandidateTr> {
+    #[derive(Deserialize)]
+    struct NonceResponseTr {
+        participant_id: String,
+        commitments: frost_secp256k1_tr::round1::SigningCommitments,
+    }
+    let resp: NonceResponseTr = client
+        .post(format!("{url}/nonce"))
+        .json(&NonceRequest { message: hex::encode(message) })
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    // secp256k1 identifiers serialize as 32-byte big-endian scalars, so they
+    // are decoded whole rather than read as a little-endian u16 prefix.
+    let id_bytes = <Vec<u8>>::from_hex(resp.participant_id)?;
+    let identifier = frost_secp256k1_tr::Identifier::deserialize(&id_bytes)
+        .map_err(|e| anyhow!(format!("identifier err: {:?}", e)))?;
+
+    Ok(CandidateTr {
+        url: url.to_string(),
+        identifier,
+        commitments: resp.commitments,
+    })
+}
+
+async fn request_share_tr(
+    client: &Client,
+    candidate: &CandidateTr,
+    signing_package: &frost_secp256k1_tr::SigningPackage,
+) -> Result<frost_secp256k1_tr::round2::SignatureShare> {
+    #[derive(Serialize)]
+    struct SignRequestTr<'a> {
+        package: &'a frost_secp256k1_tr::SigningPackage,
+    }
+    #[derive(Deserialize)]
+    struct SignResponseTr {
+        share: frost_secp256k1_tr::round2::SignatureShare,
+    }
+    let resp: SignResponseTr = client
+        .post(format!("{}/sign", candidate.url))
+        .json(&SignRequestTr { package: signing_package })
+        .send()
+        .await?
+        .json()
+        .await?;
+    Ok(resp.share)
+}
 
-        // send sign request
-        let sign_resp: SignResponse = client
-            .post(format!("{url}/sign"))
-            .json(&SignRequest {
-                package: signing_package.clone(),
-            })
-            .send()
-            .await?
-            .json()
-            .await?;
+async fn coordinator_run_tr(args: &Args, message: &[u8]) -> Result<CoordinatorOutcomeTr> {
+    let client = Client::new();
+
+    let pool: Vec<String> = args
+        .signers
+        .split(',')
+        .map(|s| s.trim().to_owned())
+        .filter(|s| !s.is_empty())
+        .collect();
 
-        shares.insert(identifier, sign_resp.share);
+    if pool.len() < args.threshold as usize {
+        return Err(anyhow!("signer pool ({}) is smaller than the threshold ({})", pool.len(), args.threshold));
     }
 
-    // 3. Load PublicKeyPackage from group_public_key.json
     let gkf: GroupKeyFile = serde_json::from_reader(File::open(&args.group_key)?)?;
+    let merkle_root = args.merkle_root_hex.as_deref().map(hex::decode).transpose()?;
+    let pubkey_package = load_pubkey_package_tr(gkf, merkle_root.as_deref())?;
+    let output_key_bytes = pubkey_package.verifying_key().serialize().map_err(|e| anyhow!(format!("vk serialize err: {:?}", e)))?;
+
+    let results = join_all(pool.iter().map(|url| fetch_candidate_tr(&client, url, message))).await;
+    let mut candidates: Vec<CandidateTr> = Vec::new();
+    for (url, result) in pool.iter().zip(results) {
+        match result {
+            Ok(candidate) => candidates.push(candidate),
+            Err(e) => warn!("signer {url} did not return round1 commitments: {e}"),
+        }
+    }
+    if candidates.len() < args.threshold as usize {
+        return Err(anyhow!(
+            "only {} of {} signers responded to round1, need {}",
+            candidates.len(),
+            pool.len(),
+            args.threshold
+        ));
+    }
 
-    // Convert verifying_shares
+    let mut active: Vec<CandidateTr> = candidates.drain(..args.threshold as usize).collect();
+    let mut standbys: Vec<CandidateTr> = candidates;
+
+    loop {
+        let commitments_map: BTreeMap<frost_secp256k1_tr::Identifier, frost_secp256k1_tr::round1::SigningCommitments> =
+            active.iter().map(|c| (c.identifier, c.commitments)).collect();
+        let signing_package = frost_secp256k1_tr::SigningPackage::new(commitments_map, message);
+
+        let share_results = join_all(active.iter().map(|c| request_share_tr(&client, c, &signing_package))).await;
+
+        let mut shares: BTreeMap<frost_secp256k1_tr::Identifier, frost_secp256k1_tr::round2::SignatureShare> = BTreeMap::new();
+        let mut culprits: Vec<usize> = Vec::new();
+        for (idx, (candidate, result)) in active.iter().zip(share_results).enumerate() {
+            let share = match result {
+                Ok(share) => share,
+                Err(e) => {
+                    warn!("signer {} ({}) failed to produce a share: {e}", candidate.url, hex::encode(candidate.identifier.serialize()));
+                    culprits.push(idx);
+                    continue;
+                }
+            };
+
+            let verifying_share = pubkey_package
+                .verifying_shares()
+                .get(&candidate.identifier)
+                .ok_or_else(|| anyhow!("no verifying share for {}", hex::encode(candidate.identifier.serialize())))?;
+
+            match frost_core::verify_signature_share::<frost_secp256k1_tr::Secp256K1Sha256TR>(
+                candidate.identifier,
+                verifying_share,
+                &share,
+                &signing_package,
+                pubkey_package.verifying_key(),
+            ) {
+                Ok(()) => {
+                    shares.insert(candidate.identifier, share);
+                }
+                Err(e) => {
+                    warn!(
+                        "signer {} ({}) submitted an invalid signature share and is being excluded: {e:?}",
+                        candidate.url,
+                        hex::encode(candidate.identifier.serialize())
+                    );
+                    culprits.push(idx);
+                }
+            }
+        }
+
+        if culprits.is_empty() {
+            info!("collected a verified quorum of {} shares, aggregating", shares.len());
+            let signature = frost_secp256k1_tr::aggregate(&signing_package, &shares, &pubkey_package)
+                .map_err(|e| anyhow!(format!("aggregate err: {:?}", e)))?;
+            return Ok(CoordinatorOutcomeTr {
+                signature: signature.serialize().map_err(|e| anyhow!(format!("serialize err: {:?}", e)))?,
+                output_key_hex: hex::encode(output_key_bytes),
+            });
+        }
+
+        for idx in culprits.into_iter().rev() {
+            active.remove(idx);
+        }
+        // Members kept in the quorum spent their nonces on the /sign just
+        // made; fetch fresh commitments for them before re-signing.
+        let refreshed = join_all(active.iter().map(|c| fetch_candidate_tr(&client, &c.url, message))).await;
+        active = refreshed
+            .into_iter()
+            .filter_map(|r| r.map_err(|e| warn!("signer dropped while refreshing commitments: {e}")).ok())
+            .collect();
+        while active.len() < args.threshold as usize {
+            match standbys.pop() {
+                Some(replacement) => active.push(replacement),
+                None => return Err(anyhow!("not enough honest, responsive signers left in the pool to reach the threshold")),
+            }
+        }
+    }
+}
+
+fn load_pubkey_package_tr(gkf: GroupKeyFile, merkle_root: Option<&[u8]>) -> Result<frost_secp256k1_tr::keys::PublicKeyPackage> {
+    let mut verifying_shares: BTreeMap<frost_secp256k1_tr::Identifier, frost_secp256k1_tr::keys::VerifyingShare> = BTreeMap::new();
+    for (id_hex, share_hex) in gkf.verifying_shares {
+        let id_bytes = <Vec<u8>>::from_hex(&id_hex)?;
+        let identifier = frost_secp256k1_tr::Identifier::deserialize(&id_bytes)
+            .map_err(|e| anyhow!(format!("identifier err: {:?}", e)))?;
+
+        let share_bytes = <Vec<u8>>::from_hex(&share_hex)?;
+        let verifying_share = frost_secp256k1_tr::keys::VerifyingShare::deserialize(&share_bytes)
+            .map_err(|e| anyhow!(format!("verifying share deserialize err: {:?}", e)))?;
+        verifying_shares.insert(identifier, verifying_share);
+    }
+
+    let verifying_key_bytes = <Vec<u8>>::from_hex(&gkf.verifying_key)?;
+    let verifying_key = frost_secp256k1_tr::VerifyingKey::deserialize(&verifying_key_bytes)
+        .map_err(|e| anyhow!(format!("verifying key deserialize err: {:?}", e)))?;
+
+    let untweaked = frost_secp256k1_tr::keys::PublicKeyPackage::new(verifying_shares, verifying_key);
+    use frost_secp256k1_tr::keys::Tweak;
+    Ok(untweaked.tweak(merkle_root))
+}
+
+fn load_pubkey_package(gkf: GroupKeyFile) -> Result<PublicKeyPackage> {
     let mut verifying_shares: BTreeMap<frost::Identifier, VerifyingShare> = BTreeMap::new();
     for (id_hex, share_hex) in gkf.verifying_shares {
         let id_bytes = <Vec<u8>>::from_hex(&id_hex)?;
@@ -154,14 +592,8 @@ async fn coordinator_run(args: &Args, message: &[u8]) -> Result<Vec<u8>> {
         verifying_shares.insert(identifier, verifying_share);
     }
 
-    // Verifying key
     let verifying_key_bytes = <Vec<u8>>::from_hex(&gkf.verifying_key)?;
     let verifying_key = frost::VerifyingKey::deserialize(&verifying_key_bytes).map_err(|e| anyhow!(format!("verifying key deserialize err: {:?}", e)))?;
 
-    let pubkey_package = PublicKeyPackage::new(verifying_shares, verifying_key);
-
-    // 4. Aggregate
-    let signature = frost::aggregate(&signing_package, &shares, &pubkey_package).map_err(|e| anyhow!(format!("aggregate err: {:?}", e)))?;
-
-    Ok(signature.serialize().map_err(|e| anyhow!(format!("serialize err: {:?}", e)))?)
-} 
\ No newline at end of file
+    Ok(PublicKeyPackage::new(verifying_shares, verifying_key))
+}
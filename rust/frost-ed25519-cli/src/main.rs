@@ -4,13 +4,24 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use anyhow::anyhow;
 use frost_ed25519 as frost;
-use frost::keys::{KeyPackage, PublicKeyPackage};
+use frost::keys::{KeyPackage, PublicKeyPackage, VerifiableSecretSharingCommitment};
 use frost::round1::{SigningCommitments, SigningNonces};
 use frost::round2::SignatureShare;
+use reqwest::Client;
 use std::collections::BTreeMap;
-use ed25519_dalek::{Signature, Verifier, PublicKey};
-use bs58;
+use ed25519_dalek::{Signature, VerifyingKey as PublicKey};
+
+/// Which elliptic-curve ciphersuite to operate under. The distributed
+/// `Dkg`/`Repair` coordinators only support `Ed25519` today; `Secp256k1Tr`
+/// shares come from the local trusted-dealer `DkgTr` instead, a demo-only
+/// path that is not threshold-safe (see its doc comment).
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Ciphersuite {
+    Ed25519,
+    Secp256k1Tr,
+}
 
 // ========= CLI definition =========
 #[derive(Parser)]
@@ -22,12 +33,77 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Run a trusted-dealer DKG for n=3, t=2 and write share files
+    /// Coordinate a distributed FROST DKG across signer daemons running in
+    /// `dkg` mode; no party ever holds the full signing key.
     Dkg {
-        /// Output directory (defaults to current dir)
+        /// Comma-separated list of signer daemon base URLs, in identifier
+        /// order (first URL is identifier 1, and so on)
+        #[arg(long)]
+        signers: String,
+        /// Signing threshold (t)
+        #[arg(long)]
+        min_signers: u16,
+        /// Output directory for group_public_key.json (defaults to current dir)
         #[arg(long, default_value = ".")]
         out_dir: PathBuf,
     },
+    /// ⚠ DEMO-ONLY, NOT THRESHOLD-SAFE: runs trusted-dealer key generation
+    /// for secp256k1-tr (BIP340 Taproot) entirely in this process, which
+    /// means this process sees every participant's secret share at once —
+    /// exactly the single-point-of-compromise chunk0-1's distributed DKG
+    /// exists to eliminate for ed25519. No distributed DKG coordinator
+    /// analogous to `Dkg` exists for this ciphersuite yet. Requires
+    /// `--i-accept-trusted-dealer-risk` so this can't be reached by habit;
+    /// use it only for local testing, never to provision a production
+    /// group's shares.
+    DkgTr {
+        /// Number of shares to generate (n)
+        #[arg(long, default_value_t = 3)]
+        max_signers: u16,
+        /// Signing threshold (t)
+        #[arg(long, default_value_t = 2)]
+        min_signers: u16,
+        /// Output directory for s{i}.json / group_public_key.json
+        #[arg(long, default_value = ".")]
+        out_dir: PathBuf,
+        /// Hex-encoded BIP341 script-tree merkle root to bake into the
+        /// written shares and group key; omit for a key-path-only output
+        #[arg(long)]
+        merkle_root_hex: Option<String>,
+        /// Required acknowledgement that this path is a trusted-dealer
+        /// demo: this process holds every participant's secret share at
+        /// once and is not threshold-safe. Refuses to run without it.
+        #[arg(long, default_value_t = false)]
+        i_accept_trusted_dealer_risk: bool,
+    },
+    /// Rebuild a participant's share from a helper quorum without ever
+    /// reconstructing the group secret (repairable secret sharing).
+    Repair {
+        /// Comma-separated helper signer daemon base URLs, each already
+        /// serving /repair/step1 and /repair/step2 (i.e. running `Serve`)
+        #[arg(long)]
+        helpers: String,
+        /// Base URL of the participant being repaired, running
+        /// `RepairTarget` with no share.json of its own yet
+        #[arg(long)]
+        target: String,
+        /// The helper identifiers, 1-based, in the same order as `--helpers`
+        #[arg(long, value_delimiter = ',')]
+        helper_identifiers: Vec<u16>,
+        /// The identifier being repaired
+        #[arg(long)]
+        target_identifier: u16,
+        /// The group's signing threshold (t). Repair needs a quorum of at
+        /// least `t` helpers; with fewer, it's rejected upfront instead of
+        /// running three network round-trips before failing on a mismatched
+        /// verifying key.
+        #[arg(long)]
+        threshold: u16,
+        /// Path to group_public_key.json, used to confirm the repaired
+        /// share reproduces the existing group key
+        #[arg(long, default_value = "group_public_key.json")]
+        group_key: PathBuf,
+    },
     /// Sign a message with 2 shares (threshold = 2)
     Sign {
         /// Path to first share JSON (e.g. s1.json)
@@ -36,6 +112,21 @@ enum Commands {
         share2: PathBuf,
         /// Message to sign (hex)
         message_hex: String,
+        /// Rerandomize the signature (Zcash-style): valid under a freshly
+        /// tweaked `VK' = VK + ρ·B` instead of the group's ordinary key, so
+        /// two signatures from the same group can't be linked to each
+        /// other. Prints VK' (hex) alongside the signature. Only supported
+        /// for `--ciphersuite ed25519`.
+        #[arg(long)]
+        rerandomize: bool,
+        /// Ciphersuite the shares were generated under
+        #[arg(long, value_enum, default_value = "ed25519")]
+        ciphersuite: Ciphersuite,
+        /// Hex-encoded BIP341 script-tree merkle root to tweak the Taproot
+        /// output key with. Only used for `--ciphersuite secp256k1-tr`;
+        /// omit for a key-path-only output.
+        #[arg(long)]
+        merkle_root_hex: Option<String>,
     },
     /// Verify a signature produced by this CLI or the Go demo
     Verify {
@@ -43,8 +134,26 @@ enum Commands {
         pubkey_json: PathBuf,
         /// Message that was signed (hex)
         message_hex: String,
-        /// Signature in hex (64-byte Ed25519)
+        /// Signature in hex (64-byte Ed25519, or 64-byte BIP340 Schnorr)
         signature_hex: String,
+        /// Verify against this rerandomized key instead of the group's
+        /// ordinary verifying key (hex), as printed by `sign --rerandomize`
+        #[arg(long)]
+        randomized_key_hex: Option<String>,
+        /// Verify as a BIP340 Schnorr signature against this x-only Taproot
+        /// output key (hex), as printed by `sign --ciphersuite secp256k1-tr`
+        #[arg(long)]
+        taproot_output_key_hex: Option<String>,
+    },
+    /// Verify many (group_pubkey, message, signature) triples at once using
+    /// a single multiscalar multiplication instead of N independent checks
+    VerifyBatch {
+        /// Path to a JSON array of { group_pubkey_hex, message_hex, signature_hex }
+        items_path: PathBuf,
+        /// If the batch check fails, fall back to verifying each item
+        /// individually to report exactly which ones are invalid
+        #[arg(long)]
+        locate_failures: bool,
     },
 }
 
@@ -52,55 +161,124 @@ enum Commands {
 struct StoredShare {
     participant_index: u16,
     key_package: KeyPackage,
+    group_commitment: VerifiableSecretSharingCommitment,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredShareTr {
+    participant_index: u16,
+    key_package: frost_secp256k1_tr::keys::KeyPackage,
+    group_commitment: frost_secp256k1_tr::keys::VerifiableSecretSharingCommitment,
+    #[serde(default)]
+    merkle_root_hex: Option<String>,
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Dkg { out_dir } => cmd_dkg(out_dir)?,
+        Commands::Dkg { signers, min_signers, out_dir } => cmd_dkg(&signers, min_signers, out_dir)?,
+        Commands::DkgTr { max_signers, min_signers, out_dir, merkle_root_hex, i_accept_trusted_dealer_risk } =>
+            cmd_dkg_tr(max_signers, min_signers, out_dir, merkle_root_hex.as_deref(), i_accept_trusted_dealer_risk)?,
+        Commands::Repair { helpers, target, helper_identifiers, target_identifier, threshold, group_key } =>
+            cmd_repair(&helpers, &target, &helper_identifiers, target_identifier, threshold, group_key)?,
+        Commands::VerifyBatch { items_path, locate_failures } => cmd_verify_batch(items_path, locate_failures)?,
         Commands::Sign {
             share1,
             share2,
             message_hex,
-        } => cmd_sign(share1, share2, &message_hex)?,
-        Commands::Verify { pubkey_json, message_hex, signature_hex } => cmd_verify(pubkey_json, &message_hex, &signature_hex)?,
+            rerandomize,
+            ciphersuite,
+            merkle_root_hex,
+        } => match ciphersuite {
+            Ciphersuite::Ed25519 => cmd_sign(share1, share2, &message_hex, rerandomize)?,
+            Ciphersuite::Secp256k1Tr => {
+                if rerandomize {
+                    return Err(anyhow!("--rerandomize is only supported for --ciphersuite ed25519"));
+                }
+                cmd_sign_tr(share1, share2, &message_hex, merkle_root_hex.as_deref())?
+            }
+        },
+        Commands::Verify { pubkey_json, message_hex, signature_hex, randomized_key_hex, taproot_output_key_hex } =>
+            match &taproot_output_key_hex {
+                None => cmd_verify(pubkey_json, &message_hex, &signature_hex, randomized_key_hex.as_deref())?,
+                Some(output_key_hex) => cmd_verify_tr(&message_hex, &signature_hex, output_key_hex)?,
+            },
     }
 
     Ok(())
 }
 
-fn cmd_dkg(out_dir: PathBuf) -> anyhow::Result<()> {
-    let (shares, pubkey_package) = frost::keys::generate_with_dealer(
-        3, // n
-        2, // t
-        frost::keys::IdentifierList::Default,
-        &mut OsRng,
-    )?;
+/// A round1 package bundled with the sender's long-lived X25519 identity
+/// key, mirroring `Round1Bundle` on the signer daemon.
+#[derive(Serialize, Deserialize, Clone)]
+struct Round1Bundle {
+    package: serde_json::Value,
+    identity_public_key: String,
+}
 
-    let mut key_packages: BTreeMap<_, _> = BTreeMap::new();
-    for (id, secret_share) in shares {
-        let kp = frost::keys::KeyPackage::try_from(secret_share)?;
-        key_packages.insert(id, kp);
-    }
+#[derive(Deserialize)]
+struct DkgRound1Response {
+    bundle: Round1Bundle,
+}
 
-    fs::create_dir_all(&out_dir)?;
+#[derive(Serialize, Deserialize, Clone)]
+struct SealedEnvelope {
+    nonce: String,
+    ciphertext: String,
+}
 
-    // Write each share
-    let mut idx_counter: u16 = 1;
-    for (_, kp) in &key_packages {
-        let stored = StoredShare {
-            participant_index: idx_counter,
-            key_package: kp.clone(),
-        };
-        let fname = format!("s{}.json", idx_counter);
-        let path = out_dir.join(fname);
-        fs::write(path, serde_json::to_vec_pretty(&stored)?)?;
+#[derive(Serialize)]
+struct DkgRound1Request {
+    min_signers: u16,
+    max_signers: u16,
+}
+
+#[derive(Serialize)]
+struct DkgRound2Request {
+    round1_bundles: BTreeMap<String, Round1Bundle>,
+}
+
+#[derive(Deserialize)]
+struct DkgRound2Response {
+    envelopes: std::collections::HashMap<String, SealedEnvelope>,
+}
 
-        idx_counter += 1;
+#[derive(Serialize)]
+struct DkgRound3Request {
+    round1_bundles: BTreeMap<String, Round1Bundle>,
+    envelopes: std::collections::HashMap<String, SealedEnvelope>,
+}
+
+#[derive(Deserialize)]
+struct DkgRound3Response {
+    public_key_package: PublicKeyPackage,
+}
+
+/// Coordinate the three-round FROST DKG across the given signer daemons
+/// (each must already be listening in `dkg` mode). The coordinator only
+/// ever relays: round1 commitments/proofs are public, and round2 traffic
+/// is end-to-end sealed between daemons, so no plaintext share evaluation
+/// passes through this process.
+fn cmd_dkg(signers: &str, min_signers: u16, out_dir: PathBuf) -> anyhow::Result<()> {
+    let urls: Vec<String> = signers
+        .split(',')
+        .map(|s| s.trim().to_owned())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let max_signers = urls.len() as u16;
+    if max_signers < 2 {
+        return Err(anyhow!("need at least two signer daemons"));
+    }
+    if min_signers < 2 || min_signers > max_signers {
+        return Err(anyhow!("min_signers must be between 2 and the number of signers"));
     }
 
-    // Augment with base58 address for convenience
+    let rt = tokio::runtime::Runtime::new()?;
+    let pub_pkg = rt.block_on(async { dkg_coordinate(&urls, min_signers, max_signers).await })?;
+
+    fs::create_dir_all(&out_dir)?;
+
     #[derive(Serialize)]
     struct PubOut<'a> {
         #[serde(flatten)]
@@ -108,9 +286,9 @@ fn cmd_dkg(out_dir: PathBuf) -> anyhow::Result<()> {
         address_base58: String,
         public_key_hex: String,
     }
-    let pk_bytes = pubkey_package.verifying_key().serialize()?;
+    let pk_bytes = pub_pkg.verifying_key().serialize()?;
     let pub_out = PubOut {
-        inner: &pubkey_package,
+        inner: &pub_pkg,
         address_base58: bs58::encode(&pk_bytes).into_string(),
         public_key_hex: hex::encode(&pk_bytes),
     };
@@ -118,11 +296,280 @@ fn cmd_dkg(out_dir: PathBuf) -> anyhow::Result<()> {
     let pub_path = out_dir.join("group_public_key.json");
     fs::write(pub_path, serde_json::to_vec_pretty(&pub_out)?)?;
 
-    println!("✅ DKG complete. Wrote shares and group public key to {:?}", out_dir);
+    println!("✅ Distributed DKG complete. Signer daemons hold their shares; wrote group public key to {:?}", out_dir);
+    Ok(())
+}
+
+async fn dkg_coordinate(urls: &[String], min_signers: u16, max_signers: u16) -> anyhow::Result<PublicKeyPackage> {
+    let client = Client::new();
+
+    // Round 1: ask every daemon to sample its polynomial and publish a
+    // commitment + proof of knowledge.
+    let mut round1_bundles: BTreeMap<String, Round1Bundle> = BTreeMap::new();
+    for (idx, url) in urls.iter().enumerate() {
+        let id_hex = hex::encode(frost::Identifier::try_from((idx + 1) as u16)?.serialize());
+        let resp: DkgRound1Response = client
+            .post(format!("{url}/dkg/round1"))
+            .json(&DkgRound1Request { min_signers, max_signers })
+            .send()
+            .await?
+            .json()
+            .await?;
+        round1_bundles.insert(id_hex, resp.bundle);
+    }
+
+    // Round 2: every daemon verifies the others' round1 packages and ships
+    // each peer a sealed, private polynomial evaluation.
+    let mut incoming: std::collections::HashMap<String, std::collections::HashMap<String, SealedEnvelope>> =
+        std::collections::HashMap::new();
+    for (idx, url) in urls.iter().enumerate() {
+        let own_hex = hex::encode(frost::Identifier::try_from((idx + 1) as u16)?.serialize());
+        let resp: DkgRound2Response = client
+            .post(format!("{url}/dkg/round2"))
+            .json(&DkgRound2Request { round1_bundles: round1_bundles.clone() })
+            .send()
+            .await?
+            .json()
+            .await?;
+        for (recipient_hex, envelope) in resp.envelopes {
+            incoming
+                .entry(recipient_hex)
+                .or_default()
+                .insert(own_hex.clone(), envelope);
+        }
+    }
+
+    // Round 3: hand each daemon the sealed envelopes addressed to it; it
+    // decrypts, sums, and writes its own share file.
+    let mut public_key_package: Option<PublicKeyPackage> = None;
+    for (idx, url) in urls.iter().enumerate() {
+        let own_hex = hex::encode(frost::Identifier::try_from((idx + 1) as u16)?.serialize());
+        let envelopes = incoming.remove(&own_hex).unwrap_or_default();
+        let resp: DkgRound3Response = client
+            .post(format!("{url}/dkg/round3"))
+            .json(&DkgRound3Request { round1_bundles: round1_bundles.clone(), envelopes })
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if let Some(existing) = &public_key_package {
+            if existing.verifying_key().serialize()? != resp.public_key_package.verifying_key().serialize()? {
+                return Err(anyhow!("signer daemons disagree on the resulting group public key"));
+            }
+        } else {
+            public_key_package = Some(resp.public_key_package);
+        }
+    }
+
+    public_key_package.ok_or_else(|| anyhow!("no signers participated"))
+}
+
+// ========= Repairable share recovery coordinator =========
+
+#[derive(Serialize)]
+struct RepairStep1Request {
+    helper_identifiers: Vec<String>,
+    target_identifier: String,
+    threshold: u16,
+    helper_identity_keys: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct RepairStep1Response {
+    deltas: std::collections::HashMap<String, SealedEnvelope>,
+}
+
+#[derive(Serialize)]
+struct RepairStep2HelperRequest {
+    deltas: std::collections::HashMap<String, SealedEnvelope>,
+    helper_identity_keys: BTreeMap<String, String>,
+    target_identity_key: String,
+}
+
+#[derive(Deserialize)]
+struct RepairStep2HelperResponse {
+    sigma_for_target: SealedEnvelope,
+    group_commitment: VerifiableSecretSharingCommitment,
+}
+
+#[derive(Serialize)]
+struct RepairStep2TargetRequest {
+    sigmas: std::collections::HashMap<String, SealedEnvelope>,
+    helper_identity_keys: BTreeMap<String, String>,
+    group_commitment: VerifiableSecretSharingCommitment,
+    group_verifying_key: frost::VerifyingKey,
+}
+
+#[derive(Deserialize)]
+struct RepairStep2TargetResponse {
+    participant_index: u16,
+}
+
+#[derive(Deserialize)]
+struct IdentityResponse {
+    identity_public_key: String,
+}
+
+fn cmd_repair(
+    helpers: &str,
+    target: &str,
+    helper_identifiers: &[u16],
+    target_identifier: u16,
+    threshold: u16,
+    group_key: PathBuf,
+) -> anyhow::Result<()> {
+    let helper_urls: Vec<String> = helpers
+        .split(',')
+        .map(|s| s.trim().to_owned())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if helper_urls.len() != helper_identifiers.len() {
+        return Err(anyhow!("--helpers and --helper-identifiers must have the same length"));
+    }
+    if helper_identifiers.contains(&target_identifier) {
+        return Err(anyhow!("target identifier must not be a helper"));
+    }
+    if (helper_identifiers.len() as u16) < threshold {
+        return Err(anyhow!(
+            "need at least {threshold} helpers to repair (--threshold), got {}",
+            helper_identifiers.len()
+        ));
+    }
+
+    #[derive(Deserialize)]
+    struct GroupKeyFile {
+        verifying_key: String,
+    }
+    let gkf: GroupKeyFile = serde_json::from_reader(std::fs::File::open(&group_key)?)?;
+    let vk_bytes = hex::decode(&gkf.verifying_key)?;
+    let group_verifying_key = frost::VerifyingKey::deserialize(&vk_bytes)
+        .map_err(|e| anyhow!(format!("verifying key deserialize err: {:?}", e)))?;
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let idx = rt.block_on(async {
+        repair_coordinate(&helper_urls, helper_identifiers, target, target_identifier, threshold, group_verifying_key).await
+    })?;
+
+    println!("✅ Repair complete. Participant {idx} has been rebuilt by the target daemon.");
     Ok(())
 }
 
-fn cmd_sign(share1_path: PathBuf, share2_path: PathBuf, message_hex: &str) -> anyhow::Result<()> {
+async fn repair_coordinate(
+    helper_urls: &[String],
+    helper_identifiers: &[u16],
+    target_url: &str,
+    target_identifier: u16,
+    threshold: u16,
+    group_verifying_key: frost::VerifyingKey,
+) -> anyhow::Result<u16> {
+    let client = Client::new();
+
+    let helper_id_hexes: Vec<String> = helper_identifiers
+        .iter()
+        .map(|id| Ok(hex::encode(frost::Identifier::try_from(*id)?.serialize())))
+        .collect::<anyhow::Result<Vec<String>>>()?;
+    let target_id_hex = hex::encode(frost::Identifier::try_from(target_identifier)?.serialize());
+
+    // Learn every participant's long-lived X25519 identity key up front so
+    // round2-style traffic can be sealed point-to-point without the
+    // coordinator ever seeing plaintext.
+    let mut helper_identity_keys: BTreeMap<String, String> = BTreeMap::new();
+    for (url, id_hex) in helper_urls.iter().zip(helper_id_hexes.iter()) {
+        let resp: IdentityResponse = client.get(format!("{url}/identity")).send().await?.json().await?;
+        helper_identity_keys.insert(id_hex.clone(), resp.identity_public_key);
+    }
+    let target_identity: IdentityResponse = client.get(format!("{target_url}/identity")).send().await?.json().await?;
+
+    // Step 1: each helper splits its scaled share into per-helper deltas,
+    // sealed for the recipient that owns each one.
+    let mut deltas_by_recipient: std::collections::HashMap<String, std::collections::HashMap<String, SealedEnvelope>> =
+        std::collections::HashMap::new();
+    for (url, id_hex) in helper_urls.iter().zip(helper_id_hexes.iter()) {
+        let resp: RepairStep1Response = client
+            .post(format!("{url}/repair/step1"))
+            .json(&RepairStep1Request {
+                helper_identifiers: helper_id_hexes.clone(),
+                target_identifier: target_id_hex.clone(),
+                threshold,
+                helper_identity_keys: helper_identity_keys.clone(),
+            })
+            .send()
+            .await?
+            .json()
+            .await?;
+        for (recipient_hex, envelope) in resp.deltas {
+            deltas_by_recipient
+                .entry(recipient_hex)
+                .or_default()
+                .insert(id_hex.clone(), envelope);
+        }
+    }
+
+    // Step 2 (helper role): each helper sums the deltas addressed to it and
+    // seals the resulting σ_j for the target.
+    let mut sigmas: std::collections::HashMap<String, SealedEnvelope> = std::collections::HashMap::new();
+    let mut group_commitment: Option<VerifiableSecretSharingCommitment> = None;
+    for (url, id_hex) in helper_urls.iter().zip(helper_id_hexes.iter()) {
+        let deltas = deltas_by_recipient.remove(id_hex).unwrap_or_default();
+        let resp: RepairStep2HelperResponse = client
+            .post(format!("{url}/repair/step2"))
+            .json(&RepairStep2HelperRequest {
+                deltas,
+                helper_identity_keys: helper_identity_keys.clone(),
+                target_identity_key: target_identity.identity_public_key.clone(),
+            })
+            .send()
+            .await?
+            .json()
+            .await?;
+        sigmas.insert(id_hex.clone(), resp.sigma_for_target);
+        group_commitment.get_or_insert(resp.group_commitment);
+    }
+
+    let group_commitment = group_commitment.ok_or_else(|| anyhow!("no helpers participated"))?;
+
+    // Step 2 (target role): the freshly-provisioned target sums all σ_j
+    // into its repaired share.
+    let resp: RepairStep2TargetResponse = client
+        .post(format!("{target_url}/repair/step2"))
+        .json(&RepairStep2TargetRequest {
+            sigmas,
+            helper_identity_keys,
+            group_commitment,
+            group_verifying_key,
+        })
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    Ok(resp.participant_index)
+}
+
+/// Derive a per-signing randomizer bound to the message plus fresh entropy.
+/// Identical construction to the aggregator's local-network signing path,
+/// duplicated here rather than shared since each binary in this workspace
+/// already carries its own copy of the small types and helpers it needs.
+fn derive_randomizer(message: &[u8]) -> frost_rerandomized::Randomizer<frost::Ed25519Sha512> {
+    use curve25519_dalek::scalar::Scalar;
+    use sha2::{Digest, Sha512};
+
+    let mut entropy = [0u8; 32];
+    rand_core::RngCore::fill_bytes(&mut OsRng, &mut entropy);
+
+    let mut hasher = Sha512::new();
+    hasher.update(b"cb-mpc/frost-rerandomize");
+    hasher.update(message);
+    hasher.update(entropy);
+    let hash: [u8; 64] = hasher.finalize().into();
+
+    let scalar_bytes = Scalar::from_bytes_mod_order_wide(&hash).to_bytes();
+    frost_rerandomized::Randomizer::deserialize(&scalar_bytes)
+        .expect("a uniformly reduced scalar is always a valid randomizer")
+}
+
+fn cmd_sign(share1_path: PathBuf, share2_path: PathBuf, message_hex: &str, rerandomize: bool) -> anyhow::Result<()> {
     // Load shares
     let s1_bytes = fs::read(&share1_path)?;
     let s1: StoredShare = serde_json::from_slice(&s1_bytes)?;
@@ -132,8 +579,8 @@ fn cmd_sign(share1_path: PathBuf, share2_path: PathBuf, message_hex: &str) -> an
     let msg_bytes = hex::decode(message_hex.trim())?;
 
     // Generate nonces & commitments
-    let nonce1 = SigningNonces::new(&s1.key_package.signing_share(), &mut OsRng);
-    let nonce2 = SigningNonces::new(&s2.key_package.signing_share(), &mut OsRng);
+    let nonce1 = SigningNonces::new(s1.key_package.signing_share(), &mut OsRng);
+    let nonce2 = SigningNonces::new(s2.key_package.signing_share(), &mut OsRng);
 
     let comm1 = SigningCommitments::from(&nonce1);
     let comm2 = SigningCommitments::from(&nonce2);
@@ -144,44 +591,387 @@ fn cmd_sign(share1_path: PathBuf, share2_path: PathBuf, message_hex: &str) -> an
 
     let signing_package = frost::SigningPackage::new(comm_map, &msg_bytes);
 
-    // Each participant creates signing share
-    let share1: SignatureShare = frost::round2::sign(&signing_package, &nonce1, &s1.key_package)?;
-    let share2: SignatureShare = frost::round2::sign(&signing_package, &nonce2, &s2.key_package)?;
+    // Load public key package from same folder as share1 (group_public_key.json)
+    let pub_dir = share1_path.parent().map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    let pub_path = pub_dir.join("group_public_key.json");
+    let pub_data = fs::read(&pub_path)?;
+    let pubkey_package: PublicKeyPackage = serde_json::from_slice(&pub_data)?;
+
+    if !rerandomize {
+        // Each participant creates signing share
+        let share1: SignatureShare = frost::round2::sign(&signing_package, &nonce1, &s1.key_package)?;
+        let share2: SignatureShare = frost::round2::sign(&signing_package, &nonce2, &s2.key_package)?;
+
+        let mut share_map: BTreeMap<_, _> = BTreeMap::new();
+        share_map.insert(*s1.key_package.identifier(), share1);
+        share_map.insert(*s2.key_package.identifier(), share2);
+
+        let group_signature = frost::aggregate(&signing_package, &share_map, &pubkey_package)?;
+        println!("{}", hex::encode(group_signature.serialize()?));
+        return Ok(());
+    }
+
+    let randomizer = derive_randomizer(&msg_bytes);
+    let randomized_params = frost_rerandomized::RandomizedParams::from_randomizer(pubkey_package.verifying_key(), randomizer);
+
+    let share1: SignatureShare = frost_rerandomized::sign(&signing_package, &nonce1, &s1.key_package, randomizer)?;
+    let share2: SignatureShare = frost_rerandomized::sign(&signing_package, &nonce2, &s2.key_package, randomizer)?;
 
-    // Combine signature shares
     let mut share_map: BTreeMap<_, _> = BTreeMap::new();
     share_map.insert(*s1.key_package.identifier(), share1);
     share_map.insert(*s2.key_package.identifier(), share2);
 
-    // Load public key package from same folder as share1 (group_public_key.json)
+    let group_signature = frost_rerandomized::aggregate(&signing_package, &share_map, &pubkey_package, &randomized_params)?;
+    let randomized_vk_bytes = randomized_params.randomized_verifying_key().serialize()?;
+
+    println!("{}", hex::encode(group_signature.serialize()?));
+    println!("Rerandomized verifying key (hex): {}", hex::encode(&randomized_vk_bytes));
+    println!("Rerandomized address (base58): {}", bs58::encode(&randomized_vk_bytes).into_string());
+    Ok(())
+}
+
+/// ⚠ DEMO-ONLY, NOT THRESHOLD-SAFE. Trusted-dealer key generation for
+/// secp256k1-tr: splits a single freshly sampled secret into `max_signers`
+/// shares of which any `min_signers` can sign, writing them in the exact
+/// `StoredShareTr` shape `Serve --ciphersuite secp256k1-tr` and
+/// `cmd_sign_tr` already expect. Unlike `frost-keygen`'s reconstruction
+/// helper, the full secret never leaves this process as a standalone value
+/// after the split — only a `SecretShare` per participant gets written out
+/// — but this process still sees every share at once while splitting them,
+/// the same single-point-of-compromise chunk0-1's distributed DKG exists to
+/// eliminate for ed25519. Refuses to run without
+/// `--i-accept-trusted-dealer-risk`.
+fn cmd_dkg_tr(
+    max_signers: u16,
+    min_signers: u16,
+    out_dir: PathBuf,
+    merkle_root_hex: Option<&str>,
+    i_accept_trusted_dealer_risk: bool,
+) -> anyhow::Result<()> {
+    if !i_accept_trusted_dealer_risk {
+        return Err(anyhow!(
+            "DkgTr is a trusted-dealer demo: this process holds every participant's \
+             secret share at once and is NOT threshold-safe. Re-run with \
+             --i-accept-trusted-dealer-risk to acknowledge this and proceed anyway."
+        ));
+    }
+    eprintln!(
+        "⚠ DkgTr: generating all {max_signers} secp256k1-tr shares in this single process. \
+         This is a demo/debug path, not a threshold-safe key generation ceremony — \
+         use it only for local testing."
+    );
+
+    if max_signers < 2 {
+        return Err(anyhow!("need at least two signers"));
+    }
+    if min_signers < 2 || min_signers > max_signers {
+        return Err(anyhow!("min_signers must be between 2 and max_signers"));
+    }
+
+    let (shares, untweaked_pubkey_package) = frost_secp256k1_tr::keys::generate_with_dealer(
+        max_signers,
+        min_signers,
+        frost_secp256k1_tr::keys::IdentifierList::Default,
+        OsRng,
+    )?;
+
+    fs::create_dir_all(&out_dir)?;
+
+    for (idx_counter, secret_share) in (1_u16..).zip(shares.values()) {
+        let group_commitment = secret_share.commitment().clone();
+        let key_package = frost_secp256k1_tr::keys::KeyPackage::try_from(secret_share.clone())?;
+        let stored = StoredShareTr {
+            participant_index: idx_counter,
+            key_package,
+            group_commitment,
+            merkle_root_hex: merkle_root_hex.map(str::to_owned),
+        };
+        let path = out_dir.join(format!("s{idx_counter}.json"));
+        fs::write(path, serde_json::to_vec_pretty(&stored)?)?;
+    }
+
+    let merkle_root = merkle_root_hex.map(hex::decode).transpose()?;
+    let tweaked_pubkey_package = {
+        use frost_secp256k1_tr::keys::Tweak;
+        untweaked_pubkey_package.clone().tweak(merkle_root.as_deref())
+    };
+    let output_key_bytes = tweaked_pubkey_package.verifying_key().serialize()?;
+
+    #[derive(Serialize)]
+    struct PubOutTr<'a> {
+        #[serde(flatten)]
+        inner: &'a frost_secp256k1_tr::keys::PublicKeyPackage,
+        taproot_output_key_hex: String,
+    }
+    let pub_out = PubOutTr {
+        inner: &untweaked_pubkey_package,
+        taproot_output_key_hex: hex::encode(output_key_bytes),
+    };
+    let pub_path = out_dir.join("group_public_key.json");
+    fs::write(pub_path, serde_json::to_vec_pretty(&pub_out)?)?;
+
+    println!("✅ Trusted-dealer secp256k1-tr key generation complete. Wrote shares and group public key to {:?}", out_dir);
+    Ok(())
+}
+
+/// Secp256k1-Taproot counterpart of `cmd_sign`: same local 2-of-n signing
+/// flow, over the BIP340 ciphersuite, with every participant's key package
+/// tweaked with the same merkle root per BIP341 before signing (tweaking
+/// with mismatched roots would make the shares combine into the wrong key).
+fn cmd_sign_tr(share1_path: PathBuf, share2_path: PathBuf, message_hex: &str, merkle_root_hex: Option<&str>) -> anyhow::Result<()> {
+    let s1: StoredShareTr = serde_json::from_slice(&fs::read(&share1_path)?)?;
+    let s2: StoredShareTr = serde_json::from_slice(&fs::read(&share2_path)?)?;
+
+    let merkle_root = merkle_root_hex.map(hex::decode).transpose()?;
+    let (key_package1, key_package2) = {
+        use frost_secp256k1_tr::keys::Tweak;
+        (s1.key_package.tweak(merkle_root.as_deref()), s2.key_package.tweak(merkle_root.as_deref()))
+    };
+
+    let msg_bytes = hex::decode(message_hex.trim())?;
+
+    let nonce1 = frost_secp256k1_tr::round1::SigningNonces::new(key_package1.signing_share(), &mut OsRng);
+    let nonce2 = frost_secp256k1_tr::round1::SigningNonces::new(key_package2.signing_share(), &mut OsRng);
+    let comm1 = frost_secp256k1_tr::round1::SigningCommitments::from(&nonce1);
+    let comm2 = frost_secp256k1_tr::round1::SigningCommitments::from(&nonce2);
+
+    let mut comm_map: BTreeMap<_, _> = BTreeMap::new();
+    comm_map.insert(*key_package1.identifier(), comm1);
+    comm_map.insert(*key_package2.identifier(), comm2);
+    let signing_package = frost_secp256k1_tr::SigningPackage::new(comm_map, &msg_bytes);
+
+    let share1 = frost_secp256k1_tr::round2::sign(&signing_package, &nonce1, &key_package1)?;
+    let share2 = frost_secp256k1_tr::round2::sign(&signing_package, &nonce2, &key_package2)?;
+
+    let mut share_map: BTreeMap<_, _> = BTreeMap::new();
+    share_map.insert(*key_package1.identifier(), share1);
+    share_map.insert(*key_package2.identifier(), share2);
+
     let pub_dir = share1_path.parent().map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
-    let pub_path = pub_dir.join("group_public_key.json");
-    let pub_data = fs::read(&pub_path)?;
-    let pubkey_package: PublicKeyPackage = serde_json::from_slice(&pub_data)?;
+    let pub_data = fs::read(pub_dir.join("group_public_key.json"))?;
+    let untweaked_pubkey_package: frost_secp256k1_tr::keys::PublicKeyPackage = serde_json::from_slice(&pub_data)?;
+    let pubkey_package = {
+        use frost_secp256k1_tr::keys::Tweak;
+        untweaked_pubkey_package.tweak(merkle_root.as_deref())
+    };
+
+    let group_signature = frost_secp256k1_tr::aggregate(&signing_package, &share_map, &pubkey_package)?;
+    let output_key_bytes = pubkey_package.verifying_key().serialize()?;
+
+    println!("{}", hex::encode(group_signature.serialize()?));
+    println!("Taproot output key (x-only hex): {}", hex::encode(output_key_bytes));
+    Ok(())
+}
 
-    let group_signature = frost::aggregate(&signing_package, &share_map, &pubkey_package)?;
+fn cmd_verify_tr(message_hex: &str, sig_hex: &str, output_key_hex: &str) -> anyhow::Result<()> {
+    let vk_bytes = hex::decode(output_key_hex)?;
+    let vk = frost_secp256k1_tr::VerifyingKey::deserialize(&vk_bytes)
+        .map_err(|e| anyhow!(format!("output key deserialize err: {:?}", e)))?;
 
-    // Serialize and output signature in hex
-    let sig_bytes = group_signature.serialize()?;
-    println!("{}", hex::encode(sig_bytes));
+    let msg = hex::decode(message_hex.trim())?;
+    let sig_bytes = hex::decode(sig_hex.trim())?;
+    let signature = frost_secp256k1_tr::Signature::deserialize(&sig_bytes)
+        .map_err(|e| anyhow!(format!("signature deserialize err: {:?}", e)))?;
+
+    match vk.verify(&msg, &signature) {
+        Ok(()) => println!("✅ Signature verified"),
+        Err(e) => println!("❌ Verification failed: {:?}", e),
+    }
     Ok(())
 }
 
-fn cmd_verify(pub_path: PathBuf, message_hex: &str, sig_hex: &str) -> anyhow::Result<()> {
+fn cmd_verify(pub_path: PathBuf, message_hex: &str, sig_hex: &str, randomized_key_hex: Option<&str>) -> anyhow::Result<()> {
     let pub_data = fs::read(pub_path)?;
     let pub_pkg: PublicKeyPackage = serde_json::from_slice(&pub_data)?;
 
-    // Extract 32-byte group public key
-    let vk_bytes = pub_pkg.verifying_key().serialize()?;
-    let vk = PublicKey::from_bytes(&vk_bytes)?;
+    // Normally the group's own public key verifies the signature; when the
+    // signature was produced with `sign --rerandomize`, the caller must pass
+    // the rerandomized VK' printed at signing time instead.
+    let vk_bytes = match randomized_key_hex {
+        Some(hex_str) => hex::decode(hex_str)?,
+        None => pub_pkg.verifying_key().serialize()?,
+    };
+    let vk_arr: [u8; 32] = vk_bytes.as_slice().try_into().map_err(|_| anyhow!("verifying key must be 32 bytes"))?;
+    let vk = PublicKey::from_bytes(&vk_arr)?;
 
     let msg = hex::decode(message_hex.trim())?;
     let sig_bytes = hex::decode(sig_hex.trim())?;
-    let sig = Signature::from_bytes(&sig_bytes)?;
+    let sig_arr: [u8; 64] = sig_bytes.as_slice().try_into().map_err(|_| anyhow!("signature must be 64 bytes"))?;
+    let sig = Signature::from_bytes(&sig_arr);
 
     match vk.verify_strict(&msg, &sig) {
         Ok(_) => println!("✅ Signature verified"),
         Err(e) => println!("❌ Verification failed: {}", e),
     }
     Ok(())
-} 
\ No newline at end of file
+}
+
+// ========= Batch verification =========
+
+#[derive(Deserialize)]
+struct BatchItem {
+    group_pubkey_hex: String,
+    message_hex: String,
+    signature_hex: String,
+}
+
+/// One batch item reduced to the curve points and scalars the identity
+/// check needs: `R`, `VK`, `z` (from the signature) and the Ed25519
+/// challenge `c = SHA-512(R || VK || M)`.
+struct ParsedItem {
+    r: curve25519_dalek::edwards::EdwardsPoint,
+    vk: curve25519_dalek::edwards::EdwardsPoint,
+    z: curve25519_dalek::scalar::Scalar,
+    c: curve25519_dalek::scalar::Scalar,
+}
+
+fn parse_item(item: &BatchItem) -> anyhow::Result<ParsedItem> {
+    use curve25519_dalek::edwards::CompressedEdwardsY;
+    use curve25519_dalek::scalar::Scalar;
+    use sha2::{Digest, Sha512};
+
+    let vk_bytes = hex::decode(&item.group_pubkey_hex)?;
+    let sig_bytes = hex::decode(&item.signature_hex)?;
+    let msg = hex::decode(item.message_hex.trim())?;
+    if vk_bytes.len() != 32 {
+        return Err(anyhow!("group_pubkey must be 32 bytes"));
+    }
+    if sig_bytes.len() != 64 {
+        return Err(anyhow!("signature must be 64 bytes"));
+    }
+
+    let vk_arr: [u8; 32] = vk_bytes.as_slice().try_into().expect("checked length above");
+    let r_arr: [u8; 32] = sig_bytes[..32].try_into().expect("checked length above");
+    let vk = CompressedEdwardsY(vk_arr)
+        .decompress()
+        .ok_or_else(|| anyhow!("group pubkey is not a valid curve point"))?;
+    let r = CompressedEdwardsY(r_arr)
+        .decompress()
+        .ok_or_else(|| anyhow!("signature R is not a valid curve point"))?;
+    let z_bytes: [u8; 32] = sig_bytes[32..64].try_into().expect("checked length above");
+    let z: Scalar = Option::from(Scalar::from_canonical_bytes(z_bytes))
+        .ok_or_else(|| anyhow!("signature s is not canonical"))?;
+
+    let mut hasher = Sha512::new();
+    hasher.update(&sig_bytes[..32]);
+    hasher.update(&vk_bytes);
+    hasher.update(&msg);
+    let hash: [u8; 64] = hasher.finalize().into();
+    let c = Scalar::from_bytes_mod_order_wide(&hash);
+
+    Ok(ParsedItem { r, vk, z, c })
+}
+
+/// Draw an independent 128-bit blinding scalar, as in the standard Ed25519
+/// batch-verification construction (a full 256-bit scalar is unnecessary
+/// since its only job is to make forging a *combined* check as hard as
+/// forging an individual signature).
+fn random_blinding_scalar() -> curve25519_dalek::scalar::Scalar {
+    let mut bytes = [0u8; 32];
+    rand_core::RngCore::fill_bytes(&mut OsRng, &mut bytes[..16]);
+    curve25519_dalek::scalar::Scalar::from_bytes_mod_order(bytes)
+}
+
+/// Check `(-Σ b_i·z_i)·B + Σ b_i·R_i + Σ (b_i·c_i)·VK_i == 0` as a single
+/// multiscalar multiplication. Returns true iff every signature in `items`
+/// is valid (modulo the blinding scalars' negligible false-accept chance).
+fn batch_verify(items: &[ParsedItem]) -> bool {
+    use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use curve25519_dalek::traits::{Identity, VartimeMultiscalarMul};
+
+    let blindings: Vec<Scalar> = items.iter().map(|_| random_blinding_scalar()).collect();
+
+    let neg_z_sum: Scalar = -items
+        .iter()
+        .zip(blindings.iter())
+        .map(|(item, b)| b * item.z)
+        .fold(Scalar::ZERO, |acc, x| acc + x);
+
+    let mut scalars: Vec<Scalar> = Vec::with_capacity(1 + items.len() * 2);
+    let mut points: Vec<EdwardsPoint> = Vec::with_capacity(1 + items.len() * 2);
+
+    scalars.push(neg_z_sum);
+    points.push(ED25519_BASEPOINT_POINT);
+
+    for (item, b) in items.iter().zip(blindings.iter()) {
+        scalars.push(*b);
+        points.push(item.r);
+        scalars.push(b * item.c);
+        points.push(item.vk);
+    }
+
+    let result = EdwardsPoint::vartime_multiscalar_mul(scalars.iter(), points.iter());
+    result == EdwardsPoint::identity()
+}
+
+fn cmd_verify_batch(items_path: PathBuf, locate_failures: bool) -> anyhow::Result<()> {
+    let raw: Vec<BatchItem> = serde_json::from_reader(fs::File::open(&items_path)?)?;
+    if raw.is_empty() {
+        return Err(anyhow!("no items to verify"));
+    }
+
+    let parsed: Vec<ParsedItem> = raw.iter().map(parse_item).collect::<anyhow::Result<_>>()?;
+
+    if batch_verify(&parsed) {
+        println!("✅ All {} signatures verified (batch check)", parsed.len());
+        return Ok(());
+    }
+
+    println!("❌ Batch check failed: at least one signature is invalid");
+    if locate_failures {
+        for (idx, item) in parsed.iter().enumerate() {
+            let ok = batch_verify(std::slice::from_ref(item));
+            println!("  item {idx}: {}", if ok { "valid" } else { "INVALID" });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed_item(message: &[u8]) -> BatchItem {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let signature = signing_key.sign(message);
+        BatchItem {
+            group_pubkey_hex: hex::encode(signing_key.verifying_key().to_bytes()),
+            message_hex: hex::encode(message),
+            signature_hex: hex::encode(signature.to_bytes()),
+        }
+    }
+
+    #[test]
+    fn batch_verify_accepts_all_valid_signatures() {
+        let items = vec![
+            parse_item(&signed_item(b"hello")).unwrap(),
+            parse_item(&signed_item(b"world")).unwrap(),
+            parse_item(&signed_item(b"")).unwrap(),
+        ];
+        assert!(batch_verify(&items));
+    }
+
+    #[test]
+    fn batch_verify_rejects_a_single_tampered_signature() {
+        let mut tampered = signed_item(b"hello");
+        let mut sig_bytes = hex::decode(&tampered.signature_hex).unwrap();
+        sig_bytes[63] ^= 0x01;
+        tampered.signature_hex = hex::encode(sig_bytes);
+
+        let items = vec![
+            parse_item(&signed_item(b"hello")).unwrap(),
+            parse_item(&tampered).unwrap(),
+            parse_item(&signed_item(b"world")).unwrap(),
+        ];
+        assert!(!batch_verify(&items));
+    }
+
+    #[test]
+    fn batch_verify_accepts_single_item_batch() {
+        let items = vec![parse_item(&signed_item(b"solo")).unwrap()];
+        assert!(batch_verify(&items));
+    }
+}
\ No newline at end of file
@@ -1,3 +1,16 @@
+// ⚠ DELIBERATELY DANGEROUS, DEBUG-ONLY: this tool reconstructs the full
+// group signing key from two shares into a single scalar, which is exactly
+// the single-point-of-compromise threshold signing exists to avoid — whoever
+// runs this, or whoever gets a copy of its output, holds the entire group's
+// key. It is not part of the normal signing workflow: `frost-ed25519-cli
+// sign` signs with the shares directly and never reconstructs the key, and
+// a lost share should be recovered with repairable secret sharing
+// (`frost-signer-daemon RepairTarget` driven by `frost-ed25519-cli repair`,
+// i.e. `cmd_repair`), which rebuilds the missing
+// share without any process ever holding the full secret. The only reason
+// this still exists is to produce a raw Solana keypair file for cases that
+// genuinely need a single non-threshold key (e.g. seeding a throwaway
+// devnet account) — require an explicit flag so it can't be run by habit.
 use frost_ed25519 as frost;
 use frost::keys::KeyPackage;
 use serde::Deserialize;
@@ -9,16 +22,25 @@ struct Stored {
     key_package: KeyPackage,
 }
 
+const RISK_FLAG: &str = "--i-accept-full-key-reconstruction-risk";
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = std::env::args().collect();
-    if args.len() != 4 {
-        eprintln!("Usage: frost-keygen <s1.json> <s2.json> <out-keypair.json>");
+    if args.len() != 5 || args[1] != RISK_FLAG {
+        eprintln!(
+            "Usage: frost-keygen {RISK_FLAG} <s1.json> <s2.json> <out-keypair.json>\n\n\
+             This reconstructs the full group signing key into a single file — the same \
+             single-point-of-compromise threshold signing exists to avoid. Not part of the \
+             normal signing or recovery workflow; see `frost-ed25519-cli sign` and \
+             `frost-signer-daemon RepairTarget` instead. Pass {RISK_FLAG} to acknowledge and \
+             proceed anyway."
+        );
         std::process::exit(1);
     }
 
     // Load the two share files
-    let s1: Stored = serde_json::from_reader(File::open(&args[1])?)?;
-    let s2: Stored = serde_json::from_reader(File::open(&args[2])?)?;
+    let s1: Stored = serde_json::from_reader(File::open(&args[2])?)?;
+    let s2: Stored = serde_json::from_reader(File::open(&args[3])?)?;
 
     // Reconstruct the signing key (scalar)
     let signing_key = frost::keys::reconstruct(&[s1.key_package, s2.key_package])
@@ -39,7 +61,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     out_vec.extend_from_slice(&sk_bytes);
     out_vec.extend_from_slice(&pk_bytes);
 
-    serde_json::to_writer_pretty(File::create(&args[3])?, &out_vec)?;
-    println!("✅ Keypair written to {}", &args[3]);
+    serde_json::to_writer_pretty(File::create(&args[4])?, &out_vec)?;
+    println!("✅ Keypair written to {}", &args[4]);
     Ok(())
 }
@@ -13,10 +13,15 @@ use hex::FromHex;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Comma-separated list of signer daemon base URLs
+    /// Comma-separated pool of signer daemon base URLs (may exceed the
+    /// threshold; extras serve as standbys)
     #[arg(long)]
     signers: String,
 
+    /// Signing threshold (t) to pass through to the aggregator
+    #[arg(long)]
+    threshold: u16,
+
     /// Path to group_public_key.json
     #[arg(long, default_value = "frost-artifacts/group_public_key.json")]
     group_key: PathBuf,
@@ -83,6 +88,7 @@ fn main() -> Result<()> {
     let output = Command::new(&args.aggregator_bin)
         .arg("--msg-hex").arg(&msg_hex)
         .arg("--signers").arg(&args.signers)
+        .arg("--threshold").arg(args.threshold.to_string())
         .arg("--group-key").arg(args.group_key.to_str().unwrap())
         .output()?;
 